@@ -0,0 +1,20 @@
+use chrono::Local;
+use criterion::{criterion_group, criterion_main, Criterion};
+use ranking::*;
+
+/// Build a synthetic dataset of `rows` play logs spread across `players`
+/// distinct ids, so aggregation has to collapse many logs per player.
+fn synthetic(rows: usize, players: usize) -> Vec<PlayLog> {
+    let now = Local::now();
+    (0..rows)
+        .map(|i| PlayLog::new(format!("player{:06}", i % players), (i % 10_000) as f64, now))
+        .collect()
+}
+
+fn bench_mean(c: &mut Criterion) {
+    let logs = synthetic(1_000_000, 10_000);
+    c.bench_function("mean_1m_rows", |b| b.iter(|| logs.mean()));
+}
+
+criterion_group!(benches, bench_mean);
+criterion_main!(benches);
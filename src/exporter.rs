@@ -0,0 +1,54 @@
+//! Output subsystem turning computed rankings into InfluxDB line protocol so
+//! leaderboard history can be pushed into InfluxDB/Grafana for trend
+//! visualization instead of only printed as CSV.
+
+use crate::PlayLogExt;
+
+/// Escape the characters InfluxDB reserves in tag values (spaces, commas and
+/// equals signs) by prefixing them with a backslash.
+fn escape_tag(value: &str) -> String {
+    value
+        .replace(' ', "\\ ")
+        .replace(',', "\\,")
+        .replace('=', "\\=")
+}
+
+/// Render `rankings` as InfluxDB line protocol, one line per entry:
+///
+/// ```text
+/// <measurement>,player_id=<id> rank=<rank>i,score=<score> <unix_nanos>
+/// ```
+///
+/// Tags (`player_id`) are comma-joined onto the measurement, fields follow
+/// after a space, and the nanosecond timestamp from `create_timestamp()` comes
+/// last.
+pub fn to_line_protocol<M: PlayLogExt>(rankings: &[(usize, M)], measurement: &str) -> String {
+    rankings
+        .iter()
+        .map(|(rank, log)| {
+            let nanos = log.create_timestamp().timestamp_nanos_opt().unwrap_or_default();
+            format!(
+                "{measurement},player_id={} rank={rank}i,score={} {nanos}",
+                escape_tag(&log.id()),
+                log.score(),
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod exporter_test {
+    use super::*;
+    use crate::PlayLog;
+    use chrono::{Local, TimeZone};
+
+    #[test]
+    fn line_protocol_shape() {
+        let ts = Local.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        let rankings = vec![(1usize, PlayLog::new("player 0001".into(), 1234., ts))];
+        let out = to_line_protocol(&rankings, "leaderboard");
+        let nanos = ts.timestamp_nanos_opt().unwrap();
+        assert_eq!(out, format!("leaderboard,player_id=player\\ 0001 rank=1i,score=1234 {nanos}"));
+    }
+}
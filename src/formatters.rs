@@ -1,37 +1,193 @@
-const DATETIME_FORMAT: &str ="%Y/%m/%d %H:%M:%S";
+use std::cell::RefCell;
+
+use anyhow::{anyhow, Result};
+use chrono::{offset::TimeZone, DateTime, Local, NaiveDateTime};
+
+const DATETIME_FORMAT: &str = "%Y/%m/%d %H:%M:%S";
+
+/// Configurable set of datetime layouts used when (de)serializing
+/// `create_timestamp`.
+///
+/// Multiple input patterns are tried in order during deserialization while a
+/// single canonical pattern is used for serialization. The [`Default`] mirrors
+/// the crate's original behavior — the single `%Y/%m/%d %H:%M:%S` layout with
+/// missing trailing seconds padded with `:00` — so existing inputs keep
+/// parsing untouched.
+#[derive(Debug, Clone)]
+pub struct DateTimeFormat {
+    inputs: Vec<String>,
+    epoch_seconds: bool,
+    pad_seconds: bool,
+    canonical: String,
+}
+
+impl Default for DateTimeFormat {
+    fn default() -> Self {
+        DateTimeFormat {
+            inputs: vec![DATETIME_FORMAT.to_string()],
+            epoch_seconds: false,
+            pad_seconds: true,
+            canonical: DATETIME_FORMAT.to_string(),
+        }
+    }
+}
+
+impl DateTimeFormat {
+    /// Start building a custom format.
+    pub fn builder() -> DateTimeFormatBuilder {
+        DateTimeFormatBuilder::default()
+    }
+
+    /// Parse `raw` as epoch seconds first when enabled, then by trying each
+    /// configured input pattern in order.
+    pub fn parse(&self, raw: &str) -> Result<DateTime<Local>> {
+        if self.epoch_seconds {
+            if let Ok(secs) = raw.trim().parse::<i64>() {
+                if let Some(dt) = Local.timestamp_opt(secs, 0).single() {
+                    return Ok(dt);
+                }
+            }
+        }
+
+        // Pad missing trailing seconds with ":00" (legacy behavior), but only
+        // as a fallback so minute-precision patterns still match the raw input.
+        let padded = self.pad_seconds.then(|| {
+            let mut s = raw.to_string();
+            let mut colon_count = s.matches(':').count();
+            while colon_count < 2 {
+                s.push_str(":00");
+                colon_count += 1;
+            }
+            s
+        });
+
+        for pattern in &self.inputs {
+            if let Ok(val) = NaiveDateTime::parse_from_str(raw, pattern) {
+                return Ok(Local.from_local_datetime(&val).unwrap());
+            }
+            if let Some(padded) = padded.as_deref().filter(|p| *p != raw) {
+                if let Ok(val) = NaiveDateTime::parse_from_str(padded, pattern) {
+                    return Ok(Local.from_local_datetime(&val).unwrap());
+                }
+            }
+        }
+
+        Err(anyhow!("no configured datetime format matched {raw:?}"))
+    }
+
+    /// Render `date` using the canonical pattern.
+    pub fn format(&self, date: &DateTime<Local>) -> String {
+        format!("{}", date.format(&self.canonical))
+    }
+
+    /// Install `self` as the active format for serde (de)serialization on the
+    /// current thread. Consulted by [`datetime_serde_format`].
+    pub fn set_current(self) {
+        CURRENT.with(|current| *current.borrow_mut() = self);
+    }
+
+    fn with_current<R>(f: impl FnOnce(&DateTimeFormat) -> R) -> R {
+        CURRENT.with(|current| f(&current.borrow()))
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<DateTimeFormat> = RefCell::new(DateTimeFormat::default());
+}
+
+/// Builder for [`DateTimeFormat`].
+#[derive(Debug, Default)]
+pub struct DateTimeFormatBuilder {
+    inputs: Vec<String>,
+    epoch_seconds: bool,
+    canonical: Option<String>,
+}
+
+impl DateTimeFormatBuilder {
+    /// Register an additional input pattern, tried after the ones already added.
+    pub fn accept(mut self, pattern: impl Into<String>) -> Self {
+        self.inputs.push(pattern.into());
+        self
+    }
+
+    /// Also accept bare epoch-second integers during deserialization.
+    pub fn epoch_seconds(mut self, enabled: bool) -> Self {
+        self.epoch_seconds = enabled;
+        self
+    }
+
+    /// Set the canonical pattern used for serialization. Defaults to the first
+    /// registered input pattern.
+    pub fn canonical(mut self, pattern: impl Into<String>) -> Self {
+        self.canonical = Some(pattern.into());
+        self
+    }
+
+    pub fn build(self) -> DateTimeFormat {
+        let canonical = self
+            .canonical
+            .or_else(|| self.inputs.first().cloned())
+            .unwrap_or_else(|| DATETIME_FORMAT.to_string());
+        let inputs = if self.inputs.is_empty() {
+            vec![DATETIME_FORMAT.to_string()]
+        } else {
+            self.inputs
+        };
+        DateTimeFormat {
+            inputs,
+            epoch_seconds: self.epoch_seconds,
+            // Keep the legacy ":00" seconds padding so seconds-less inputs like
+            // `%Y/%m/%d %H:%M` still parse against a `%H:%M:%S` pattern.
+            pad_seconds: true,
+            canonical,
+        }
+    }
+}
+
+/// (De)serialize `PlayLog::tags` as a single `;`-joined column so a real CSV
+/// `tags` field round-trips (the `csv` crate cannot deserialize a sequence
+/// from one field). An empty or absent value yields an empty `Vec`.
+pub mod tags_serde {
+    use serde::{self, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(tags: &[String], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&tags.join(";"))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        if s.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        Ok(s.split(';').map(|tag| tag.trim().to_string()).collect())
+    }
+}
 
 pub mod datetime_serde_format {
-    use chrono::{offset::TimeZone, DateTime, Local, NaiveDateTime};
+    use chrono::{DateTime, Local};
     use serde::{self, Deserialize, Deserializer, Serializer};
 
-    use super::DATETIME_FORMAT;
+    use super::DateTimeFormat;
 
     pub fn serialize<S>(date: &DateTime<Local>, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let s = format!("{}", date.format(&DATETIME_FORMAT));
-        return serializer.serialize_str(&s);
+        let s = DateTimeFormat::with_current(|fmt| fmt.format(date));
+        serializer.serialize_str(&s)
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Local>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let mut s = String::deserialize(deserializer)?;
-        let mut colon_count = s.matches(":").count();
-
-        // Add Trailing Zeros for Missing DateTime data
-        while colon_count < 2 {
-            s.push_str(":00");
-            colon_count += 1;
-        }
-
-        match NaiveDateTime::parse_from_str(&s, &DATETIME_FORMAT).map_err(serde::de::Error::custom) {
-            Ok(val) => {
-                return Ok(Local.from_local_datetime(&val).unwrap());
-            },
-            Err(e) => return Err(e),
-        }
+        let s = String::deserialize(deserializer)?;
+        DateTimeFormat::with_current(|fmt| fmt.parse(&s)).map_err(serde::de::Error::custom)
     }
 }
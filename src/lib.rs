@@ -1,14 +1,17 @@
+pub mod exporter;
 pub mod formatters;
 
 use std::collections::HashMap;
+use std::path::Path;
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Datelike, Duration, Local, Timelike};
 use serde::{Deserialize, Serialize};
 use derive_new::new;
 use getset::{Getters, Setters, CopyGetters};
 use anyhow::Result;
 
 use formatters::datetime_serde_format;
+pub use formatters::{DateTimeFormat, DateTimeFormatBuilder};
 
 #[derive(new, Debug, Clone, Serialize, Deserialize, Getters, CopyGetters, Setters)]
 pub struct PlayLog {
@@ -18,6 +21,58 @@ pub struct PlayLog {
     #[getset(get = "pub")]
     #[serde(with = "datetime_serde_format")]
     create_timestamp: DateTime<Local>,
+    /// Free-form labels (e.g. game mode, region, platform). Encoded as a single
+    /// `;`-joined CSV column; defaults to empty so sources without a `tags`
+    /// column keep deserializing.
+    #[getset(set = "pub")]
+    #[new(default)]
+    #[serde(default, with = "formatters::tags_serde")]
+    tags: Vec<String>,
+}
+
+/// Builder for the CSV ingest path used by `main`.
+///
+/// Configure an accepted [`DateTimeFormat`] so different CSV sources parse
+/// without code edits; defaults to the crate's original layout.
+pub struct PlayLogReader {
+    format: DateTimeFormat,
+}
+
+impl PlayLogReader {
+    pub fn new() -> Self {
+        PlayLogReader { format: DateTimeFormat::default() }
+    }
+
+    /// Use `format` for timestamp (de)serialization while reading.
+    pub fn with_format(mut self, format: DateTimeFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Read and deserialize every record at `path`, skipping (and logging)
+    /// lines that fail to parse.
+    pub fn read_path<P: AsRef<Path>>(self, path: P) -> Result<Vec<PlayLog>> {
+        self.format.set_current();
+
+        let mut reader = csv::Reader::from_path(path)?;
+        let mut records: Vec<PlayLog> = vec![];
+        for (i, record) in reader.deserialize().enumerate() {
+            match record {
+                Ok(record) => records.push(record),
+                Err(err) => {
+                    tracing::error!("Fail to resolve Data of Line:{i}");
+                    tracing::error!("{err}");
+                },
+            }
+        }
+        Ok(records)
+    }
+}
+
+impl Default for PlayLogReader {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PlayLogExt for PlayLog {
@@ -32,13 +87,93 @@ impl PlayLogExt for PlayLog {
     fn set_score(&mut self, score: f64) -> &mut Self {
         self.score = score;
         self
-    } 
+    }
+
+    fn create_timestamp(&self) -> DateTime<Local> {
+        self.create_timestamp
+    }
+
+    fn tags(&self) -> Vec<String> {
+        self.tags.clone()
+    }
 }
 
 pub trait PlayLogExt {
     fn id(&self) -> String;
     fn score(&self) -> f64;
     fn set_score(&mut self, score: f64) -> &mut Self;
+    fn create_timestamp(&self) -> DateTime<Local>;
+    fn tags(&self) -> Vec<String>;
+}
+
+/// Leaderboards keyed by each bucket's start timestamp, as returned by
+/// [`Ranking::windowed_rankings`].
+pub type WindowedRankings<M> = HashMap<DateTime<Local>, Vec<(usize, M)>>;
+
+/// Strategy used by [`Ranking::aggregate`] to collapse a player's many play
+/// logs into a single score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregation {
+    /// Arithmetic mean of every score.
+    Mean,
+    /// Highest score.
+    Best,
+    /// Score of the most recent `create_timestamp`.
+    Latest,
+    /// 50th percentile.
+    Median,
+    /// Linear-interpolated percentile `p` in `[0, 100]`.
+    Percentile(f64),
+}
+
+/// Linear-interpolated percentile over an ascending-sorted slice.
+///
+/// `p` is clamped to `[0, 100]`; `rank = p/100 * (n - 1)` is split into its
+/// floor/ceil neighbours and interpolated.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.;
+    }
+    let p = p.clamp(0., 100.);
+    let rank = p / 100. * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    let frac = rank - lo as f64;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * frac
+}
+
+/// Time bucket used by [`Ranking::windowed_rankings`].
+///
+/// Each `PlayLog`'s `create_timestamp` is floored to the start of the window
+/// it belongs to, except for `Rolling`, which produces one snapshot per
+/// distinct timestamp covering a trailing `Duration`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    Daily,
+    Weekly,
+    Monthly,
+    Rolling(Duration),
+}
+
+impl Window {
+    /// Floor `ts` to the start of the window it falls in.
+    ///
+    /// `Rolling` has no fixed boundary, so the timestamp is returned as-is.
+    fn floor(&self, ts: DateTime<Local>) -> DateTime<Local> {
+        match self {
+            Window::Daily => ts
+                .with_hour(0).unwrap()
+                .with_minute(0).unwrap()
+                .with_second(0).unwrap()
+                .with_nanosecond(0).unwrap(),
+            Window::Weekly => {
+                let start_of_day = Window::Daily.floor(ts);
+                start_of_day - Duration::days(ts.weekday().num_days_from_monday() as i64)
+            },
+            Window::Monthly => Window::Daily.floor(ts).with_day(1).unwrap(),
+            Window::Rolling(_) => ts,
+        }
+    }
 }
 
 /// This Trait is Implement For Vector of Playing. 
@@ -67,31 +202,122 @@ pub trait PlayLogExt {
 pub trait Ranking<M: PlayLogExt> {
     // Return Ranking and PlayLog Structure
     fn mean(&self) -> Vec<M>;
+
+    /// Collapse each player's logs into a single `M` using `strategy`,
+    /// preserving first-seen ordering.
+    fn aggregate(&self, strategy: Aggregation) -> Vec<M>;
+
     fn top_rankings(&self, top_rank: usize) -> Result<Vec<(usize, M)>>;
+
+    /// Return only the logs carrying `tag`, so a mixed CSV can be reduced to a
+    /// per-mode or per-region subset before `aggregate`/`top_rankings`.
+    fn filter_by_tag(&self, tag: &str) -> Vec<M>;
+
+    /// Bucket the logs by `window`, aggregate each bucket with [`Ranking::mean`],
+    /// and return the `top_rank` leaderboard per bucket keyed by the bucket's
+    /// start timestamp.
+    ///
+    /// For [`Window::Rolling`] one snapshot is emitted per distinct timestamp,
+    /// containing only logs whose timestamp falls within `[t - duration, t]`.
+    fn windowed_rankings(&self, window: Window, top_rank: usize) -> Result<WindowedRankings<M>>;
+}
+
+/// Per-player running accumulator used by [`Ranking::aggregate`].
+///
+/// The running mean is updated with the `mean = mean/(k+1)*k + score/(k+1)`
+/// recurrence so no scores need to be retained for the `Mean` strategy; raw
+/// scores are only collected for `Median`/`Percentile`, which need them sorted.
+struct Accumulator<M> {
+    rep: M,
+    count: usize,
+    running_mean: f64,
+    best: f64,
+    latest_ts: DateTime<Local>,
+    latest_score: f64,
+    scores: Vec<f64>,
+}
+
+impl<M: PlayLogExt + Clone> Accumulator<M> {
+    fn new(rep: M, strategy: Aggregation) -> Self {
+        let score = rep.score();
+        let latest_ts = rep.create_timestamp();
+        let mut acc = Accumulator {
+            rep,
+            count: 0,
+            running_mean: 0.,
+            best: f64::MIN,
+            latest_ts,
+            latest_score: score,
+            scores: vec![],
+        };
+        // Seed with the first element itself.
+        acc.observe(score, latest_ts, strategy);
+        acc
+    }
+
+    fn push(&mut self, element: &M, strategy: Aggregation) {
+        self.observe(element.score(), element.create_timestamp(), strategy);
+    }
+
+    fn observe(&mut self, score: f64, ts: DateTime<Local>, strategy: Aggregation) {
+        let k = self.count as f64;
+        self.running_mean = self.running_mean / (k + 1.) * k + score / (k + 1.);
+        self.count += 1;
+
+        self.best = self.best.max(score);
+        if ts >= self.latest_ts {
+            self.latest_ts = ts;
+            self.latest_score = score;
+        }
+        if matches!(strategy, Aggregation::Median | Aggregation::Percentile(_)) {
+            self.scores.push(score);
+        }
+    }
+
+    fn finish(mut self, strategy: Aggregation) -> M {
+        let score = match strategy {
+            Aggregation::Mean => self.running_mean,
+            Aggregation::Best => self.best,
+            Aggregation::Latest => self.latest_score,
+            Aggregation::Median => {
+                self.scores.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                percentile(&self.scores, 50.)
+            },
+            Aggregation::Percentile(p) => {
+                self.scores.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                percentile(&self.scores, p)
+            },
+        };
+        self.rep.set_score(score);
+        self.rep
+    }
 }
 
 impl<M: PlayLogExt + Clone> Ranking<M> for Vec<M> {
     fn mean(&self) -> Vec<M> {
-        let mut play_counts = HashMap::new();
-        let mut res = vec![];
+        self.aggregate(Aggregation::Mean)
+    }
+
+    fn aggregate(&self, strategy: Aggregation) -> Vec<M> {
+        // Single pass keyed by player id, with a parallel `order` vector so the
+        // materialized result preserves first-seen ordering. This keeps the
+        // grouping pass O(n) instead of the former O(n^2) linear scan;
+        // `Median`/`Percentile` additionally sort each player's scores.
+        let mut order: Vec<String> = vec![];
+        let mut acc: HashMap<String, Accumulator<M>> = HashMap::new();
 
         for element in self.iter() {
-            if let Some(target) = res.iter_mut().find(|players: &&mut M| players.id() == element.id()) {
-                // Get Number of Play
-                let k = *play_counts.entry(target.id()).or_insert(0) as f64;
-
-                // Calc for Mean Score
-                let term1 = target.score() / (k + 1.) * k;
-                let term2 = element.score() / (k + 1.);
-
-                target.set_score(term1 + term2);
-                play_counts.entry(target.id()).and_modify(|count| *count += 1);
-            } else {
-                play_counts.insert(element.id(), 1);
-                res.push((*element).clone());
-            };
+            let id = element.id();
+            match acc.get_mut(&id) {
+                Some(entry) => entry.push(element, strategy),
+                None => {
+                    order.push(id.clone());
+                    acc.insert(id, Accumulator::new(element.clone(), strategy));
+                },
+            }
         }
-        res
+
+        order.into_iter().map(|id| acc.remove(&id).unwrap().finish(strategy)).collect()
     }
 
     fn top_rankings(&self, top_rank: usize) -> Result<Vec<(usize, M)>> {
@@ -117,12 +343,56 @@ impl<M: PlayLogExt + Clone> Ranking<M> for Vec<M> {
         }
         Ok(res)
     }
+
+    fn filter_by_tag(&self, tag: &str) -> Vec<M> {
+        self.iter()
+            .filter(|log| log.tags().iter().any(|t| t == tag))
+            .cloned()
+            .collect()
+    }
+
+    fn windowed_rankings(&self, window: Window, top_rank: usize) -> Result<WindowedRankings<M>> {
+        let mut res = HashMap::new();
+
+        match window {
+            Window::Rolling(duration) => {
+                // Emit one leaderboard snapshot per distinct timestamp, scoped
+                // to the trailing `duration`.
+                let mut timestamps = self.iter().map(|log| log.create_timestamp()).collect::<Vec<_>>();
+                timestamps.sort();
+                timestamps.dedup();
+
+                for t in timestamps {
+                    let bucket = self.iter()
+                        .filter(|log| {
+                            let ts = log.create_timestamp();
+                            ts <= t && ts >= t - duration
+                        })
+                        .cloned()
+                        .collect::<Vec<M>>();
+                    res.insert(t, bucket.mean().top_rankings(top_rank)?);
+                }
+            },
+            _ => {
+                let mut buckets: HashMap<DateTime<Local>, Vec<M>> = HashMap::new();
+                for log in self.iter() {
+                    buckets.entry(window.floor(log.create_timestamp())).or_default().push(log.clone());
+                }
+                for (boundary, bucket) in buckets {
+                    res.insert(boundary, bucket.mean().top_rankings(top_rank)?);
+                }
+            },
+        }
+
+        Ok(res)
+    }
 }
 
 
 #[cfg(test)]
 mod play_log_test {
     use super::*;
+    use chrono::TimeZone;
     #[test]
     fn play_log_creation() {
         tracing_subscriber::fmt::init();
@@ -175,4 +445,106 @@ create_timestamp,player_id,score
         assert_eq!(mean[0].score().round(), 1000.);
         assert_eq!(mean[1].score().round(), 1100.);
     }
+
+    #[test]
+    fn datetime_format_tries_patterns_in_order() -> anyhow::Result<()> {
+        //! A custom format accepts multiple layouts and epoch seconds.
+        let format = DateTimeFormat::builder()
+            .accept("%Y-%m-%d %H:%M:%S")
+            .accept("%Y/%m/%d %H:%M:%S")
+            .epoch_seconds(true)
+            .build();
+
+        let dashed = format.parse("2021-01-02 03:04:05")?;
+        let slashed = format.parse("2021/01/02 03:04:05")?;
+        assert_eq!(dashed, slashed);
+        assert!(format.parse("1609554245").is_ok());
+        assert!(format.parse("not a date").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn datetime_format_minute_precision() -> anyhow::Result<()> {
+        //! A minute-precision pattern matches the raw input without being
+        //! corrupted by the legacy seconds padding.
+        let format = DateTimeFormat::builder()
+            .accept("%Y-%m-%d %H:%M")
+            .build();
+
+        let minute = format.parse("2021-01-02 03:04")?;
+        assert_eq!(minute, Local.with_ymd_and_hms(2021, 1, 2, 3, 4, 0).unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn aggregate_strategies() {
+        //! Best/Latest/Median over a single player's three scores.
+        let base = Local.with_ymd_and_hms(2021, 1, 1, 12, 0, 0).unwrap();
+        let playlogs = vec![
+            PlayLog::new("player0001".into(), 100., base),
+            PlayLog::new("player0001".into(), 300., base + Duration::hours(1)),
+            PlayLog::new("player0001".into(), 200., base + Duration::hours(2)),
+        ];
+        assert_eq!(playlogs.aggregate(Aggregation::Best)[0].score(), 300.);
+        assert_eq!(playlogs.aggregate(Aggregation::Latest)[0].score(), 200.);
+        assert_eq!(playlogs.aggregate(Aggregation::Median)[0].score(), 200.);
+        assert_eq!(playlogs.aggregate(Aggregation::Mean)[0].score().round(), 200.);
+    }
+
+    #[test]
+    fn filter_by_tag_then_rank() -> anyhow::Result<()> {
+        //! A mixed log can be reduced to a single mode before ranking.
+        let now = Local::now();
+        let mut ranked = PlayLog::new("player0001".into(), 500., now);
+        ranked.set_tags(vec!["ranked".into()]);
+        let mut casual = PlayLog::new("player0002".into(), 900., now);
+        casual.set_tags(vec!["casual".into()]);
+
+        let playlogs = vec![ranked, casual];
+        let ranked_only = playlogs.filter_by_tag("ranked");
+        assert_eq!(ranked_only.len(), 1);
+
+        let top = ranked_only.aggregate(Aggregation::Best).top_rankings(10)?;
+        assert_eq!(top[0].1.id(), "player0001");
+        Ok(())
+    }
+
+    #[test]
+    fn tags_default_when_absent() {
+        //! CSV without a `tags` column still deserializes.
+        let data = "create_timestamp,player_id,score\n2021/01/01 12:00,player0001,100\n";
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+        let logs = reader.deserialize().map(|r| r.unwrap()).collect::<Vec<PlayLog>>();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].tags().is_empty());
+    }
+
+    #[test]
+    fn tags_present_column_splits() {
+        //! A `;`-joined `tags` column deserializes into multiple tags.
+        let data = "create_timestamp,player_id,score,tags\n2021/01/01 12:00,player0001,100,ranked;na\n";
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+        let logs = reader.deserialize().map(|r| r.unwrap()).collect::<Vec<PlayLog>>();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].tags(), vec!["ranked".to_string(), "na".to_string()]);
+    }
+
+    #[test]
+    fn windowed_daily() -> anyhow::Result<()> {
+        //! Logs on two distinct days should land in two daily buckets.
+        let day1 = Local.with_ymd_and_hms(2021, 1, 1, 12, 0, 0).unwrap();
+        let day2 = Local.with_ymd_and_hms(2021, 1, 2, 9, 0, 0).unwrap();
+        let playlogs = vec![
+            PlayLog::new("player0001".into(), 100., day1),
+            PlayLog::new("player0001".into(), 200., day1.with_hour(18).unwrap()),
+            PlayLog::new("player0002".into(), 50., day2),
+        ];
+        let windows = playlogs.windowed_rankings(Window::Daily, 10)?;
+        assert_eq!(windows.len(), 2);
+
+        let first = windows.get(&Window::Daily.floor(day1)).unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].1.score().round(), 150.);
+        Ok(())
+    }
 }
@@ -7,18 +7,16 @@ fn main() -> Result<()> {
     
     let path = std::env::args().nth(1).expect("No CSV path is given.");
 
-    let mut reader = csv::Reader::from_path(path).unwrap();
-    let mut records: Vec<PlayLog> = vec![];
-    for (i, record )in reader.deserialize().enumerate() {
-        match record {
-            Ok(record) => records.push(record),
-            Err(err) => {
-                tracing::error!("Fail to resolve Data of Line:{i}");
-                tracing::error!("{err}");
-                continue;
-            }
-        };
-    }
+    // Accept the crate's original layout plus a couple of common CSV sources
+    // without touching any type definitions.
+    let format = DateTimeFormat::builder()
+        .accept("%Y/%m/%d %H:%M:%S")
+        .accept("%Y-%m-%d %H:%M:%S")
+        .accept("%Y-%m-%dT%H:%M:%S")
+        .epoch_seconds(true)
+        .build();
+
+    let records = PlayLogReader::new().with_format(format).read_path(path)?;
     let records = records.mean();
     let top_10 = records.top_rankings(10)?;
     